@@ -0,0 +1,43 @@
+// Process-wide corrected clock, updated by the NTP task and read by anyone
+// that needs a timestamp (monitors, the display, the status endpoint).
+//
+// A plain pair of atomics is enough here: there's exactly one writer (the
+// NTP task) and many readers, and nothing needs to observe offset and
+// synced-ness atomically together.
+
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+static OFFSET_MS: AtomicI64 = AtomicI64::new(0);
+static SYNCED: AtomicBool = AtomicBool::new(false);
+
+/// Record a newly measured offset (true time minus local clock, in
+/// milliseconds) and mark the clock as synced.
+pub fn set_offset_ms(offset_ms: i64) {
+    OFFSET_MS.store(offset_ms, Ordering::Relaxed);
+    SYNCED.store(true, Ordering::Relaxed);
+}
+
+/// The most recently measured offset, or 0 before the first successful sync.
+pub fn offset_ms() -> i64 {
+    OFFSET_MS.load(Ordering::Relaxed)
+}
+
+/// Whether an NTP sync has ever succeeded.
+pub fn is_synced() -> bool {
+    SYNCED.load(Ordering::Relaxed)
+}
+
+/// The current time, corrected by the last measured NTP offset. Falls back
+/// to the uncorrected local clock before the first sync.
+pub fn now() -> SystemTime {
+    let corrected = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as i64
+        + offset_ms();
+    UNIX_EPOCH + std::time::Duration::from_millis(corrected.max(0) as u64)
+}
+
+/// Corrected Unix timestamp in whole seconds, the unit `SensorData` and the
+/// display already use.
+pub fn now_unix_secs() -> u64 {
+    now().duration_since(UNIX_EPOCH).unwrap().as_secs()
+}
@@ -0,0 +1,87 @@
+// YAML-defined configuration for the pluggable monitor/output pipeline.
+//
+// A config file looks like:
+//
+//   monitors:
+//     - kind: dht22
+//       pin: 4
+//   outputs:
+//     - kind: st7789
+//     - kind: mqtt
+//       host: localhost
+//       topic_prefix: pi-zero
+//
+// Each entry's extra fields are kept as a generic YAML mapping so
+// `monitors::factory`/`outputs::factory` can pull out whatever parameters
+// their `kind` needs without this module knowing about every variant.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// One entry in the `monitors` or `outputs` list.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ComponentConfig {
+    pub kind: String,
+    #[serde(flatten)]
+    pub params: HashMap<String, serde_yaml::Value>,
+}
+
+impl ComponentConfig {
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.params.get(key).and_then(|v| v.as_str())
+    }
+
+    pub fn get_u64(&self, key: &str) -> Option<u64> {
+        self.params.get(key).and_then(|v| v.as_u64())
+    }
+
+    pub fn get_str_seq(&self, key: &str) -> Option<Vec<String>> {
+        self.params.get(key).and_then(|v| v.as_sequence()).map(|seq| {
+            seq.iter()
+                .filter_map(|item| item.as_str().map(str::to_string))
+                .collect()
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct AppConfig {
+    #[serde(default)]
+    pub monitors: Vec<ComponentConfig>,
+    #[serde(default)]
+    pub outputs: Vec<ComponentConfig>,
+}
+
+impl AppConfig {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read config file {:?}", path))?;
+        serde_yaml::from_str(&raw)
+            .with_context(|| format!("failed to parse config file {:?}", path))
+    }
+
+    /// The historical hardcoded setup (one DHT22 monitor feeding the
+    /// ST7789/mock display), used when no config file is present so
+    /// existing deployments keep working without a migration step.
+    pub fn default_config() -> Self {
+        let mut dht22 = HashMap::new();
+        dht22.insert(
+            "pin".to_string(),
+            serde_yaml::Value::Number(serde_yaml::Number::from(4u64)),
+        );
+
+        AppConfig {
+            monitors: vec![ComponentConfig {
+                kind: "dht22".to_string(),
+                params: dht22,
+            }],
+            outputs: vec![ComponentConfig {
+                kind: "st7789".to_string(),
+                params: HashMap::new(),
+            }],
+        }
+    }
+}
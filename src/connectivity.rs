@@ -0,0 +1,87 @@
+// Connectivity manager: periodically probes reachability and broadcasts
+// Online/Offline transitions so other tasks can react without polling a
+// shared flag themselves.
+//
+// Modeled as a small event-driven mixin: a task implements `ConnectivityAware`
+// with the hooks it cares about, then `watch_hooks` drives those hooks from
+// a `tokio::sync::watch` channel for the lifetime of the channel.
+
+use log::info;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use tokio::net::TcpStream;
+use tokio::sync::watch;
+use tokio::time::{self, Duration};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectivityState {
+    Online,
+    Offline,
+}
+
+static ONLINE: AtomicBool = AtomicBool::new(false);
+static LAST_TRANSITION_UNIX_SECS: AtomicU64 = AtomicU64::new(0);
+
+/// Whether the device currently has network/broker reachability.
+pub fn is_online() -> bool {
+    ONLINE.load(Ordering::Relaxed)
+}
+
+/// Unix timestamp of the most recent Online/Offline transition, or 0 if
+/// none has happened yet.
+pub fn last_transition_unix_secs() -> u64 {
+    LAST_TRANSITION_UNIX_SECS.load(Ordering::Relaxed)
+}
+
+/// Implemented by tasks that need to react to connectivity transitions.
+/// Default no-op bodies mean a task only overrides the hook it cares about.
+pub trait ConnectivityAware: Send {
+    fn on_online(&mut self) {}
+    fn on_offline(&mut self) {}
+}
+
+/// Periodically probe `probe_host:probe_port` and publish Online/Offline
+/// transitions on `tx`. Probing a TCP connect to the MQTT broker is a cheap
+/// proxy for "do we have a usable network path right now".
+pub async fn connectivity_task(tx: watch::Sender<ConnectivityState>, probe_host: String, probe_port: u16) {
+    let mut interval = time::interval(Duration::from_secs(10));
+
+    loop {
+        interval.tick().await;
+
+        let reachable = time::timeout(
+            Duration::from_secs(3),
+            TcpStream::connect((probe_host.as_str(), probe_port)),
+        )
+        .await
+        .map(|connect_result| connect_result.is_ok())
+        .unwrap_or(false);
+
+        let was_online = ONLINE.swap(reachable, Ordering::Relaxed);
+        if was_online != reachable {
+            LAST_TRANSITION_UNIX_SECS.store(crate::clock::now_unix_secs(), Ordering::Relaxed);
+            let new_state = if reachable {
+                ConnectivityState::Online
+            } else {
+                ConnectivityState::Offline
+            };
+            info!("Connectivity transitioned to {:?}", new_state);
+            let _ = tx.send(new_state);
+        }
+    }
+}
+
+/// Drive a single `ConnectivityAware` target's hooks from a watch channel,
+/// starting with its current value, for as long as the channel stays open.
+pub async fn watch_hooks(mut rx: watch::Receiver<ConnectivityState>, mut target: impl ConnectivityAware) {
+    apply(&mut target, *rx.borrow());
+    while rx.changed().await.is_ok() {
+        apply(&mut target, *rx.borrow());
+    }
+}
+
+fn apply(target: &mut impl ConnectivityAware, state: ConnectivityState) {
+    match state {
+        ConnectivityState::Online => target.on_online(),
+        ConnectivityState::Offline => target.on_offline(),
+    }
+}
@@ -0,0 +1,198 @@
+// DHT22 one-wire bit-banging driver. Readings are decoded in the DHT22's
+// 16-bit humidity/temperature format; the DHT11's integer/decimal byte
+// format is not decoded.
+//
+// The sensor is driven over a single data pin: the host pulls the line low
+// to request a reading, the sensor acknowledges with a low/high pulse pair,
+// then clocks out 40 bits where each bit is a ~50us low pulse followed by a
+// high pulse whose duration encodes 0 or 1 (~26-28us = 0, ~70us = 1).
+
+use anyhow::{bail, Context, Result};
+use rppal::gpio::{Gpio, Level, Mode};
+use std::time::{Duration, Instant};
+
+/// Humidity (%RH) and temperature (C) read from a DHT22 sensor.
+pub struct Dht22Reading {
+    pub humidity: f32,
+    pub temperature: f32,
+}
+
+const START_SIGNAL_LOW: Duration = Duration::from_millis(2);
+const RESPONSE_TIMEOUT: Duration = Duration::from_millis(10);
+const BIT_THRESHOLD_US: u128 = 50;
+
+/// Bit-bang a single DHT22 reading on the given BCM GPIO pin.
+///
+/// Returns an error on checksum mismatch or if the sensor doesn't respond in
+/// time, so callers can retry on the next tick.
+pub fn read(pin_number: u8) -> Result<Dht22Reading> {
+    let gpio = Gpio::new().context("failed to access GPIO")?;
+    let mut pin = gpio.get(pin_number)?.into_io(Mode::Output);
+
+    // Send the start signal: pull low, then release to let the sensor
+    // respond.
+    pin.set_low();
+    std::thread::sleep(START_SIGNAL_LOW);
+    pin.set_high();
+    pin.set_mode(Mode::Input);
+
+    let edges = capture_edges(&pin, 83)?;
+    let bits = decode_bits(&edges)?;
+    let bytes = bits_to_bytes(&bits);
+
+    let checksum = bytes[0]
+        .wrapping_add(bytes[1])
+        .wrapping_add(bytes[2])
+        .wrapping_add(bytes[3]);
+    if checksum != bytes[4] {
+        bail!(
+            "DHT22 checksum mismatch: expected {:#x}, got {:#x}",
+            bytes[4],
+            checksum
+        );
+    }
+
+    let humidity = u16::from_be_bytes([bytes[0], bytes[1]]) as f32 / 10.0;
+    let temperature = decode_temperature(bytes[2], bytes[3]);
+
+    Ok(Dht22Reading {
+        humidity,
+        temperature,
+    })
+}
+
+/// DHT22 encodes negative temperatures with the high bit of the integer
+/// byte used as a sign flag rather than two's complement.
+fn decode_temperature(int_byte: u8, dec_byte: u8) -> f32 {
+    let magnitude = u16::from_be_bytes([int_byte & 0x7f, dec_byte]) as f32 / 10.0;
+    if int_byte & 0x80 != 0 {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+/// Record `count` level-transition timestamps: the 80us/80us sensor
+/// acknowledgement, then for each of the 40 bits the falling edge that
+/// starts its low pulse and the rising edge that starts its high pulse, then
+/// the falling edge that ends the last bit's high pulse.
+fn capture_edges(pin: &rppal::gpio::IoPin, count: usize) -> Result<Vec<(Level, Instant)>> {
+    let mut edges = Vec::with_capacity(count);
+    let mut last_level = pin.read();
+    let deadline = Instant::now() + RESPONSE_TIMEOUT;
+
+    while edges.len() < count {
+        if Instant::now() > deadline {
+            bail!(
+                "timed out waiting for DHT22 response, captured {} of {} edges",
+                edges.len(),
+                count
+            );
+        }
+        let level = pin.read();
+        if level != last_level {
+            edges.push((level, Instant::now()));
+            last_level = level;
+        }
+    }
+
+    Ok(edges)
+}
+
+/// Turn the captured edges into 40 bits by measuring the width of each
+/// bit's high pulse against the 0/1 threshold.
+fn decode_bits(edges: &[(Level, Instant)]) -> Result<Vec<bool>> {
+    // edges[0..2] are the sensor's 80us low / 80us high acknowledgement;
+    // each bit then starts with a falling edge, and its high pulse runs from
+    // the following rising edge to the next falling edge.
+    if edges.len() < 2 + 40 * 2 + 1 {
+        bail!(
+            "not enough edge transitions to decode 40 bits: got {}",
+            edges.len()
+        );
+    }
+
+    let mut bits = Vec::with_capacity(40);
+    let mut idx = 3;
+    while bits.len() < 40 {
+        let (_, high_start) = edges[idx];
+        let (_, high_end) = edges[idx + 1];
+        let high_us = high_end.duration_since(high_start).as_micros();
+        bits.push(high_us > BIT_THRESHOLD_US);
+        idx += 2;
+    }
+
+    Ok(bits)
+}
+
+fn bits_to_bytes(bits: &[bool]) -> [u8; 5] {
+    let mut bytes = [0u8; 5];
+    for (i, chunk) in bits.chunks(8).enumerate() {
+        let mut byte = 0u8;
+        for bit in chunk {
+            byte = (byte << 1) | (*bit as u8);
+        }
+        bytes[i] = byte;
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_temperature_handles_positive_values() {
+        // 0x00C8 = 200 -> 20.0 C
+        assert_eq!(decode_temperature(0x00, 0xC8), 20.0);
+    }
+
+    #[test]
+    fn decode_temperature_handles_negative_sign_bit() {
+        // high bit of the integer byte marks negative, magnitude in the
+        // remaining 15 bits: 0x000A = 10 -> -1.0 C
+        assert_eq!(decode_temperature(0x80, 0x0A), -1.0);
+    }
+
+    #[test]
+    fn bits_to_bytes_packs_msb_first() {
+        let bytes = [0xA5u8, 0x3C, 0x00, 0xFF, 0x1B];
+        let bits: Vec<bool> = bytes
+            .iter()
+            .flat_map(|b| (0..8).map(move |i| (b >> (7 - i)) & 1 == 1))
+            .collect();
+        assert_eq!(bits_to_bytes(&bits), bytes);
+    }
+
+    /// Build a synthetic edge trace: an 80us/80us ack pair, then per bit a
+    /// 50us low pulse followed by a high pulse whose width is set by
+    /// `widths_us`, ending with the falling edge that closes the last bit.
+    fn synth_edges(widths_us: &[u64]) -> Vec<(Level, Instant)> {
+        let base = Instant::now();
+        let mut edges = vec![(Level::Low, base), (Level::High, base + Duration::from_micros(80))];
+
+        let mut t = base + Duration::from_micros(160);
+        for &width in widths_us {
+            edges.push((Level::Low, t));
+            t += Duration::from_micros(50);
+            edges.push((Level::High, t));
+            t += Duration::from_micros(width);
+        }
+        edges.push((Level::Low, t));
+        edges
+    }
+
+    #[test]
+    fn decode_bits_thresholds_long_and_short_pulses() {
+        let pattern: Vec<bool> = (0..40).map(|i| i % 3 == 0).collect();
+        let widths: Vec<u64> = pattern.iter().map(|&bit| if bit { 70 } else { 30 }).collect();
+
+        assert_eq!(decode_bits(&synth_edges(&widths)).unwrap(), pattern);
+    }
+
+    #[test]
+    fn decode_bits_errors_on_too_few_edges() {
+        let edges = synth_edges(&[30; 10]);
+        assert!(decode_bits(&edges).is_err());
+    }
+}
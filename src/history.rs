@@ -0,0 +1,114 @@
+// Fixed-capacity ring buffer for recent sensor readings. Pushing is O(1)
+// (just an index bump and an overwrite), and reading takes a contiguous
+// chronological snapshot so it never blocks the writer for longer than a
+// `Vec` clone.
+
+pub struct RingBuffer<T> {
+    buf: Vec<Option<T>>,
+    capacity: usize,
+    /// Index the next `push` will write to.
+    next: usize,
+    /// Number of valid entries, capped at `capacity`.
+    len: usize,
+    /// Total number of `push` calls ever made, used as a high-water mark so
+    /// callers can ask for "everything pushed since I last looked".
+    total_pushed: u64,
+}
+
+impl<T: Clone> RingBuffer<T> {
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "ring buffer capacity must be non-zero");
+        Self {
+            buf: vec![None; capacity],
+            capacity,
+            next: 0,
+            len: 0,
+            total_pushed: 0,
+        }
+    }
+
+    /// Overwrite the oldest entry (if full) with `item`.
+    pub fn push(&mut self, item: T) {
+        self.buf[self.next] = Some(item);
+        self.next = (self.next + 1) % self.capacity;
+        self.len = (self.len + 1).min(self.capacity);
+        self.total_pushed += 1;
+    }
+
+    /// A contiguous copy of the buffered entries, oldest first.
+    pub fn snapshot(&self) -> Vec<T> {
+        let start = if self.len < self.capacity {
+            0
+        } else {
+            self.next
+        };
+
+        (0..self.len)
+            .map(|i| self.buf[(start + i) % self.capacity].clone().unwrap())
+            .collect()
+    }
+
+    /// Total number of entries ever pushed, usable as a high-water mark with
+    /// [`RingBuffer::snapshot_since`].
+    pub fn total_pushed(&self) -> u64 {
+        self.total_pushed
+    }
+
+    /// The entries pushed after `mark` (a previously observed
+    /// [`RingBuffer::total_pushed`] value), oldest first. If more than
+    /// `capacity` entries have been pushed since `mark`, only the ones still
+    /// held in the buffer are returned.
+    pub fn snapshot_since(&self, mark: u64) -> Vec<T> {
+        let new_count = self.total_pushed.saturating_sub(mark).min(self.len as u64) as usize;
+        let mut snapshot = self.snapshot();
+        snapshot.split_off(snapshot.len() - new_count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_is_chronological_before_wraparound() {
+        let mut buf = RingBuffer::new(3);
+        buf.push(1);
+        buf.push(2);
+        assert_eq!(buf.snapshot(), vec![1, 2]);
+    }
+
+    #[test]
+    fn snapshot_is_chronological_after_wraparound() {
+        let mut buf = RingBuffer::new(3);
+        for i in 1..=5 {
+            buf.push(i);
+        }
+        // Capacity 3, so only the last 3 pushes (3, 4, 5) should remain,
+        // oldest first.
+        assert_eq!(buf.snapshot(), vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn snapshot_since_returns_only_new_entries() {
+        let mut buf = RingBuffer::new(3);
+        buf.push(1);
+        buf.push(2);
+        let mark = buf.total_pushed();
+        buf.push(3);
+        buf.push(4);
+        assert_eq!(buf.snapshot_since(mark), vec![3, 4]);
+    }
+
+    #[test]
+    fn snapshot_since_caps_at_buffer_capacity() {
+        let mut buf = RingBuffer::new(3);
+        buf.push(1);
+        let mark = buf.total_pushed();
+        for i in 2..=10 {
+            buf.push(i);
+        }
+        // More entries were pushed since `mark` than the buffer can hold, so
+        // only what's still in the buffer comes back.
+        assert_eq!(buf.snapshot_since(mark), vec![8, 9, 10]);
+    }
+}
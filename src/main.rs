@@ -21,8 +21,23 @@ use rppal::spi::{Bus, Mode, SlaveSelect, Spi};
 #[cfg(feature = "hardware")]
 use st7789::{ST7789};
 
+#[cfg(feature = "hardware")]
+mod dht22;
+mod clock;
+mod config;
+mod connectivity;
+mod history;
+mod monitors;
+mod mqtt;
+mod ntp;
+mod outputs;
+
+/// How many recent sensor readings to keep for the `/history` endpoint and
+/// on-device sparkline.
+const SENSOR_HISTORY_CAPACITY: usize = 720;
+
 // Data structures for API responses
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 struct SensorData {
     temperature: f32,
     humidity: f32,
@@ -35,6 +50,11 @@ struct SystemStatus {
     led_status: bool,
     last_sensor_reading: Option<SensorData>,
     display_content: Option<String>,
+    time_synced: bool,
+    clock_offset_ms: i64,
+    ambient_light: Option<AmbientLightData>,
+    online: bool,
+    last_connectivity_transition: u64,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -42,6 +62,20 @@ struct LedControl {
     state: bool,
 }
 
+#[derive(Deserialize)]
+struct HistoryQuery {
+    limit: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct AmbientLightData {
+    illuminance_lux: f32,
+    red: u16,
+    green: u16,
+    blue: u16,
+    timestamp: u64,
+}
+
 // Mock display for simulation
 #[cfg(feature = "simulation")]
 struct MockDisplay {
@@ -98,6 +132,31 @@ impl MockDisplay {
     fn get_content(&self) -> &str {
         &self.content
     }
+
+    /// Render `values` as a one-row bar chart using block characters scaled
+    /// between the series' min and max.
+    fn add_sparkline(&mut self, values: &[f32], x: u32, y: u32) {
+        const LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+        if values.is_empty() {
+            return;
+        }
+
+        let min = values.iter().cloned().fold(f32::INFINITY, f32::min);
+        let max = values.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+        let range = (max - min).max(0.1);
+
+        let bars: String = values
+            .iter()
+            .map(|v| {
+                let normalized = (v - min) / range;
+                let level = (normalized * (LEVELS.len() - 1) as f32).round() as usize;
+                LEVELS[level.min(LEVELS.len() - 1)]
+            })
+            .collect();
+
+        self.add_text(&bars, x, y);
+    }
 }
 
 // Display trait for abstraction
@@ -105,6 +164,9 @@ trait Display {
     fn clear(&mut self) -> Result<()>;
     fn draw_text(&mut self, text: &str, x: u32, y: u32, color: Rgb565) -> Result<()>;
     fn get_display_content(&self) -> Option<String>;
+    /// Draw a min/max sparkline of `values` inside the box starting at
+    /// `(x, y)` with the given size.
+    fn draw_sparkline(&mut self, values: &[f32], x: u32, y: u32, width: u32, height: u32) -> Result<()>;
 }
 
 // Hardware display implementation
@@ -132,6 +194,40 @@ impl Display for HardwareDisplay {
     fn get_display_content(&self) -> Option<String> {
         None // Hardware display doesn't provide content string
     }
+
+    fn draw_sparkline(&mut self, values: &[f32], x: u32, y: u32, width: u32, height: u32) -> Result<()> {
+        if values.is_empty() {
+            return Ok(());
+        }
+
+        let min = values.iter().cloned().fold(f32::INFINITY, f32::min);
+        let max = values.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+        let range = (max - min).max(0.1);
+
+        let style = PrimitiveStyleBuilder::new()
+            .fill_color(Rgb565::CYAN)
+            .build();
+
+        // One narrow column per sample, scaled to fill `width` pixels. With
+        // more samples than pixels, only the most recent `width` are drawn.
+        let values = &values[values.len().saturating_sub(width as usize)..];
+        let column_width = (width as usize / values.len()).max(1) as u32;
+        for (i, value) in values.iter().enumerate() {
+            let normalized = (value - min) / range;
+            let bar_height = (normalized * height as f32) as u32;
+            let bar_x = x + (i as u32 * column_width);
+            let bar_y = y + height - bar_height;
+
+            Rectangle::new(
+                Point::new(bar_x as i32, bar_y as i32),
+                Size::new(column_width.max(1), bar_height.max(1)),
+            )
+            .into_styled(style)
+            .draw(&mut self.display)?;
+        }
+
+        Ok(())
+    }
 }
 
 // Simulation display implementation
@@ -155,6 +251,11 @@ impl Display for SimulationDisplay {
     fn get_display_content(&self) -> Option<String> {
         Some(self.mock_display.get_content().to_string())
     }
+
+    fn draw_sparkline(&mut self, values: &[f32], x: u32, y: u32, _width: u32, _height: u32) -> Result<()> {
+        self.mock_display.add_sparkline(values, x / 10, y / 20); // Scale down coordinates
+        Ok(())
+    }
 }
 
 // Shared application state
@@ -165,6 +266,8 @@ struct AppState {
     #[cfg(feature = "simulation")]
     led_status: Arc<Mutex<bool>>,
     sensor_data: Arc<Mutex<Option<SensorData>>>,
+    sensor_history: Arc<Mutex<history::RingBuffer<SensorData>>>,
+    ambient_light: Arc<Mutex<Option<AmbientLightData>>>,
     display: Arc<Mutex<Option<Box<dyn Display + Send>>>>,
     start_time: Instant,
 }
@@ -188,21 +291,25 @@ impl AppState {
             Ok(AppState {
                 led_pin: Arc::new(Mutex::new(led_pin)),
                 sensor_data: Arc::new(Mutex::new(None)),
+                sensor_history: Arc::new(Mutex::new(history::RingBuffer::new(SENSOR_HISTORY_CAPACITY))),
+                ambient_light: Arc::new(Mutex::new(None)),
                 display: Arc::new(Mutex::new(display)),
                 start_time: Instant::now(),
             })
         }
-        
+
         #[cfg(feature = "simulation")]
         {
             info!("Running in simulation mode");
             let display: Option<Box<dyn Display + Send>> = Some(Box::new(SimulationDisplay {
                 mock_display: MockDisplay::new(50, 15),
             }));
-            
+
             Ok(AppState {
                 led_status: Arc::new(Mutex::new(false)),
                 sensor_data: Arc::new(Mutex::new(None)),
+                sensor_history: Arc::new(Mutex::new(history::RingBuffer::new(SENSOR_HISTORY_CAPACITY))),
+                ambient_light: Arc::new(Mutex::new(None)),
                 display: Arc::new(Mutex::new(display)),
                 start_time: Instant::now(),
             })
@@ -241,10 +348,63 @@ async fn main() -> Result<()> {
     let app_state = AppState::new()?;
     info!("Application initialized successfully");
 
-    // Start sensor reading task
-    let sensor_state = app_state.clone();
+    // Load the monitors/outputs config, falling back to the historical
+    // hardcoded DHT22 + display setup if no config file is present.
+    let config_path = std::env::var("CONFIG_PATH").unwrap_or_else(|_| "config.yaml".to_string());
+    let config = match config::AppConfig::load(&config_path) {
+        Ok(config) => config,
+        Err(e) => {
+            warn!(
+                "Could not load config from {}, using built-in defaults: {}",
+                config_path, e
+            );
+            config::AppConfig::default_config()
+        }
+    };
+
+    let outputs = config
+        .outputs
+        .iter()
+        .filter_map(|entry| match outputs::factory(entry, &app_state) {
+            Ok(output) => Some(output),
+            Err(e) => {
+                error!("Failed to initialize output {:?}: {}", entry.kind, e);
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let monitors = config
+        .monitors
+        .iter()
+        .filter_map(|entry| match monitors::factory(entry) {
+            Ok(monitor) => Some(monitor),
+            Err(e) => {
+                error!("Failed to initialize monitor {:?}: {}", entry.kind, e);
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // Gate every monitor and the output dispatcher behind a barrier so none
+    // of them starts until all of them have been spawned. Outputs only render
+    // when a reading arrives, so the display never draws before the first one.
+    let barrier = Arc::new(tokio::sync::Barrier::new(monitors.len() + 1));
+    let (reading_tx, reading_rx) = tokio::sync::mpsc::channel(32);
+
+    for monitor in monitors {
+        let tx = reading_tx.clone();
+        let barrier = barrier.clone();
+        tokio::spawn(async move {
+            monitor_task(monitor, tx, barrier).await;
+        });
+    }
+    drop(reading_tx);
+
+    let dispatcher_state = app_state.clone();
+    let dispatcher_barrier = barrier.clone();
     tokio::spawn(async move {
-        sensor_reading_task(sensor_state).await;
+        output_dispatcher_task(reading_rx, outputs, dispatcher_state, dispatcher_barrier).await;
     });
 
     // Start LED task
@@ -253,10 +413,30 @@ async fn main() -> Result<()> {
         led_task(led_state).await;
     });
 
-    // Start display update task
-    let display_state = app_state.clone();
+    // Start the connectivity manager, probing the MQTT broker as a proxy
+    // for "do we have a usable network path right now".
+    let probe_host = std::env::var("MQTT_HOST").unwrap_or_else(|_| "localhost".to_string());
+    let probe_port: u16 = std::env::var("MQTT_PORT")
+        .ok()
+        .and_then(|p| p.parse().ok())
+        .unwrap_or(1883);
+    let (connectivity_tx, connectivity_rx) =
+        tokio::sync::watch::channel(connectivity::ConnectivityState::Offline);
+    tokio::spawn(async move {
+        connectivity::connectivity_task(connectivity_tx, probe_host, probe_port).await;
+    });
+
+    // Start the MQTT status publisher and remote LED control subscriber.
+    // Per-reading telemetry is owned by the `mqtt` output (see outputs::factory)
+    // when one is configured, so this task doesn't also publish to `<prefix>/sensor`.
+    let mqtt_state = app_state.clone();
+    tokio::spawn(async move {
+        mqtt::mqtt_task(mqtt_state, connectivity_rx).await;
+    });
+
+    // Start NTP clock sync
     tokio::spawn(async move {
-        display_update_task(display_state).await;
+        ntp::ntp_task().await;
     });
 
     // Setup web API routes
@@ -271,36 +451,74 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-// Simulated sensor reading task
-async fn sensor_reading_task(state: AppState) {
-    let mut interval = time::interval(Duration::from_secs(5));
-    
+// Runs one configured monitor on its own schedule, sending every successful
+// reading to the output dispatcher. Waits at `barrier` first so it starts in
+// lockstep with the dispatcher and every other monitor.
+async fn monitor_task(
+    mut monitor: Box<dyn monitors::Monitor>,
+    tx: tokio::sync::mpsc::Sender<monitors::Reading>,
+    barrier: Arc<tokio::sync::Barrier>,
+) {
+    barrier.wait().await;
+
+    let mut interval = time::interval(monitor.interval());
     loop {
         interval.tick().await;
-        
-        // Simulate reading temperature and humidity sensors
-        let temperature = simulate_temperature_reading();
-        let humidity = simulate_humidity_reading();
-        
-        let sensor_data = SensorData {
-            temperature,
-            humidity,
-            timestamp: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
-        };
-        
-        // Update shared state
-        if let Ok(mut data) = state.sensor_data.lock() {
-            *data = Some(sensor_data.clone());
+
+        match monitor.read() {
+            Ok(reading) => {
+                if let monitors::Reading::Sensor(data) = &reading {
+                    info!(
+                        "Sensor reading: {:.1}°C, {:.1}% humidity",
+                        data.temperature, data.humidity
+                    );
+                    if data.temperature > 30.0 {
+                        warn!("High temperature detected: {:.1}°C", data.temperature);
+                    }
+                }
+
+                if tx.send(reading).await.is_err() {
+                    break;
+                }
+            }
+            Err(e) => warn!("Monitor read failed, will retry next tick: {}", e),
         }
-        
-        info!("Sensor reading: {:.1}°C, {:.1}% humidity", temperature, humidity);
-        
-        // Log warning if temperature is too high
-        if temperature > 30.0 {
-            warn!("High temperature detected: {:.1}°C", temperature);
+    }
+}
+
+// Fans every reading out to each configured output, and keeps `AppState`'s
+// `sensor_data` up to date so the HTTP API and MQTT status topic see the
+// latest reading the same way they did when the sensor task wrote it
+// directly. Waits at `barrier` so outputs never render before monitors have
+// started producing readings.
+async fn output_dispatcher_task(
+    mut rx: tokio::sync::mpsc::Receiver<monitors::Reading>,
+    mut outputs: Vec<Box<dyn outputs::Output>>,
+    state: AppState,
+    barrier: Arc<tokio::sync::Barrier>,
+) {
+    barrier.wait().await;
+
+    while let Some(reading) = rx.recv().await {
+        if let monitors::Reading::Sensor(data) = &reading {
+            if let Ok(mut sensor_data) = state.sensor_data.lock() {
+                *sensor_data = Some(data.clone());
+            }
+            if let Ok(mut history) = state.sensor_history.lock() {
+                history.push(data.clone());
+            }
+        }
+
+        if let monitors::Reading::AmbientLight(data) = &reading {
+            if let Ok(mut ambient_light) = state.ambient_light.lock() {
+                *ambient_light = Some(data.clone());
+            }
+        }
+
+        for output in &mut outputs {
+            if let Err(e) = output.handle(&reading) {
+                error!("Output failed to handle reading: {}", e);
+            }
         }
     }
 }
@@ -335,31 +553,6 @@ async fn led_task(state: AppState) {
     }
 }
 
-// Display update task
-async fn display_update_task(state: AppState) {
-    let mut interval = time::interval(Duration::from_secs(2));
-    
-    loop {
-        interval.tick().await;
-        
-        // Get current sensor data
-        let sensor_data = if let Ok(data) = state.sensor_data.lock() {
-            data.clone()
-        } else {
-            None
-        };
-        
-        // Update display if available
-        if let Ok(mut display_opt) = state.display.lock() {
-            if let Some(ref mut display) = display_opt.as_mut() {
-                if let Err(e) = update_display_content(display.as_mut(), &sensor_data, &state) {
-                    error!("Failed to update display: {}", e);
-                }
-            }
-        }
-    }
-}
-
 // Function to update display content
 fn update_display_content(
     display: &mut dyn Display,
@@ -393,25 +586,33 @@ fn update_display_content(
     let uptime = state.start_time.elapsed().as_secs();
     let uptime_text = format!("Uptime: {}s", uptime);
     display.draw_text(&uptime_text, 10, 180, Rgb565::WHITE)?;
-    
-    // LED status indicator
-    #[cfg(feature = "hardware")]
-    let led_status = if let Ok(pin) = state.led_pin.lock() {
-        pin.is_set_high()
-    } else {
-        false
-    };
-    
-    #[cfg(feature = "simulation")]
-    let led_status = if let Ok(status) = state.led_status.lock() {
-        *status
+
+    // Clock line: corrected time if NTP has synced, otherwise a warning that
+    // timestamps can't be trusted yet
+    let clock_text = if clock::is_synced() {
+        let synced_secs = clock::now_unix_secs();
+        format!("Clock: {}s (synced)", synced_secs)
     } else {
-        false
+        "Clock: not synced".to_string()
     };
-    
+    let clock_color = if clock::is_synced() { Rgb565::WHITE } else { Rgb565::RED };
+    display.draw_text(&clock_text, 10, 210, clock_color)?;
+
+    // LED status indicator
+    let led_status = current_led_state(state);
     let led_color = if led_status { Rgb565::GREEN } else { Rgb565::RED };
-    display.draw_text("LED", 10, 210, led_color)?;
-    
+    display.draw_text("LED", 10, 240, led_color)?;
+
+    // Connectivity indicator, same green/red convention as the LED indicator
+    let connectivity_color = if connectivity::is_online() { Rgb565::GREEN } else { Rgb565::RED };
+    display.draw_text("NET", 60, 240, connectivity_color)?;
+
+    // Recent-temperature sparkline, so trends are visible without an API call
+    if let Ok(history) = state.sensor_history.lock() {
+        let temperatures: Vec<f32> = history.snapshot().iter().map(|d| d.temperature).collect();
+        display.draw_sparkline(&temperatures, 100, 230, 130, 10)?;
+    }
+
     // Print to console in simulation mode
     #[cfg(feature = "simulation")]
     {
@@ -462,6 +663,13 @@ fn setup_routes(
         .and(with_state(state.clone()))
         .and_then(get_display_handler);
 
+    // GET /history?limit=N - Recent sensor readings
+    let history_route = warp::path("history")
+        .and(warp::get())
+        .and(warp::query::<HistoryQuery>())
+        .and(with_state(state.clone()))
+        .and_then(get_history_handler);
+
     // GET / - Simple welcome message
     let hello_route = warp::path::end()
         .and(warp::get())
@@ -471,6 +679,7 @@ fn setup_routes(
         .or(sensor_route)
         .or(led_route)
         .or(display_route)
+        .or(history_route)
         .or(hello_route)
         .with(cors)
 }
@@ -485,21 +694,8 @@ fn with_state(
 // API Handlers
 async fn get_status_handler(state: AppState) -> Result<impl warp::Reply, warp::Rejection> {
     let uptime = state.start_time.elapsed().as_secs();
-    
-    #[cfg(feature = "hardware")]
-    let led_status = if let Ok(pin) = state.led_pin.lock() {
-        pin.is_set_high()
-    } else {
-        false
-    };
-    
-    #[cfg(feature = "simulation")]
-    let led_status = if let Ok(status) = state.led_status.lock() {
-        *status
-    } else {
-        false
-    };
-    
+    let led_status = current_led_state(&state);
+
     let last_sensor_reading = if let Ok(data) = state.sensor_data.lock() {
         data.clone()
     } else {
@@ -512,13 +708,24 @@ async fn get_status_handler(state: AppState) -> Result<impl warp::Reply, warp::R
         None
     };
     
+    let ambient_light = if let Ok(light) = state.ambient_light.lock() {
+        light.clone()
+    } else {
+        None
+    };
+
     let status = SystemStatus {
         uptime_seconds: uptime,
         led_status,
         last_sensor_reading,
         display_content,
+        time_synced: clock::is_synced(),
+        clock_offset_ms: clock::offset_ms(),
+        ambient_light,
+        online: connectivity::is_online(),
+        last_connectivity_transition: connectivity::last_transition_unix_secs(),
     };
-    
+
     Ok(warp::reply::json(&status))
 }
 
@@ -528,46 +735,101 @@ async fn get_sensor_handler(state: AppState) -> Result<impl warp::Reply, warp::R
     } else {
         None
     };
-    
+
+    let ambient_light = if let Ok(light) = state.ambient_light.lock() {
+        light.clone()
+    } else {
+        None
+    };
+
+    // Envelope object so the latest ambient light reading can be surfaced
+    // here as well as on `/status`, per the ambient light request.
     match sensor_data {
-        Some(data) => Ok(warp::reply::json(&data)),
+        Some(data) => Ok(warp::reply::json(&serde_json::json!({
+            "sensor": data,
+            "ambient_light": ambient_light,
+        }))),
         None => Ok(warp::reply::json(&serde_json::json!({
             "error": "No sensor data available"
         }))),
     }
 }
 
-async fn control_led_handler(
-    led_control: LedControl,
+async fn get_history_handler(
+    query: HistoryQuery,
     state: AppState,
 ) -> Result<impl warp::Reply, warp::Rejection> {
+    let history = if let Ok(history) = state.sensor_history.lock() {
+        history.snapshot()
+    } else {
+        Vec::new()
+    };
+
+    let limit = query.limit.unwrap_or(history.len()).min(history.len());
+    let recent = &history[history.len() - limit..];
+
+    Ok(warp::reply::json(recent))
+}
+
+// Read back the LED's current state, used by the status endpoint, the
+// display, and the MQTT status publisher.
+fn current_led_state(state: &AppState) -> bool {
     #[cfg(feature = "hardware")]
     {
-        if let Ok(mut pin) = state.led_pin.lock() {
-            if led_control.state {
-                pin.set_high();
-                info!("LED turned ON via API");
-            } else {
-                pin.set_low();
-                info!("LED turned OFF via API");
-            }
+        state.led_pin.lock().map(|pin| pin.is_set_high()).unwrap_or(false)
+    }
+
+    #[cfg(feature = "simulation")]
+    {
+        state.led_status.lock().map(|status| *status).unwrap_or(false)
+    }
+}
+
+// Drive the LED to the requested state. Shared by the HTTP handler and the
+// MQTT `<prefix>/led/set` subscription so both entry points behave identically.
+fn apply_led_state(state: &AppState, desired: bool) -> Result<()> {
+    #[cfg(feature = "hardware")]
+    {
+        let mut pin = state
+            .led_pin
+            .lock()
+            .map_err(|_| anyhow::anyhow!("failed to access GPIO"))?;
+        if desired {
+            pin.set_high();
         } else {
-            error!("Failed to control LED");
-            return Ok(warp::reply::json(&serde_json::json!({
-                "success": false,
-                "error": "Failed to access GPIO"
-            })));
+            pin.set_low();
         }
     }
-    
+
     #[cfg(feature = "simulation")]
     {
-        if let Ok(mut status) = state.led_status.lock() {
-            *status = led_control.state;
-            info!("LED turned {} via API (simulation)", if led_control.state { "ON" } else { "OFF" });
-        }
+        let mut status = state
+            .led_status
+            .lock()
+            .map_err(|_| anyhow::anyhow!("failed to access LED status"))?;
+        *status = desired;
     }
-    
+
+    Ok(())
+}
+
+async fn control_led_handler(
+    led_control: LedControl,
+    state: AppState,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    if let Err(e) = apply_led_state(&state, led_control.state) {
+        error!("Failed to control LED: {}", e);
+        return Ok(warp::reply::json(&serde_json::json!({
+            "success": false,
+            "error": e.to_string()
+        })));
+    }
+
+    info!(
+        "LED turned {} via API",
+        if led_control.state { "ON" } else { "OFF" }
+    );
+
     Ok(warp::reply::json(&serde_json::json!({
         "success": true,
         "led_state": led_control.state
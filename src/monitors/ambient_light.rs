@@ -0,0 +1,157 @@
+use super::{Monitor, Reading};
+use crate::config::ComponentConfig;
+use crate::AmbientLightData;
+use anyhow::{bail, Result};
+use std::time::Duration;
+
+#[cfg(feature = "hardware")]
+use rppal::i2c::I2c;
+
+/// Default I2C address for a TCS34725-style illuminance/RGB sensor.
+const DEFAULT_I2C_ADDRESS: u16 = 0x29;
+#[cfg(feature = "hardware")]
+const COMMAND_AUTO_INCREMENT: u8 = 0xA0;
+#[cfg(feature = "hardware")]
+const CDATA_REGISTER: u8 = 0x14;
+
+/// A sensor's four raw channels, in the order its driver reports them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Axis {
+    Illuminance,
+    Red,
+    Green,
+    Blue,
+}
+
+impl Axis {
+    fn parse(label: &str) -> Option<Axis> {
+        match label {
+            "illuminance" | "clear" | "lux" => Some(Axis::Illuminance),
+            "red" => Some(Axis::Red),
+            "green" => Some(Axis::Green),
+            "blue" => Some(Axis::Blue),
+            _ => None,
+        }
+    }
+}
+
+/// Ambient light sensor monitor. Some sensors report their four raw
+/// channels in driver-dependent order, so the mapping from channel index to
+/// logical field is configurable via `axis_order` rather than assumed.
+pub struct AmbientLightMonitor {
+    #[cfg_attr(not(feature = "hardware"), allow(dead_code))]
+    i2c_address: u16,
+    axis_order: Vec<Axis>,
+}
+
+impl AmbientLightMonitor {
+    pub fn from_config(config: &ComponentConfig) -> Result<Self> {
+        let i2c_address = config
+            .get_u64("i2c_address")
+            .map(|v| v as u16)
+            .unwrap_or(DEFAULT_I2C_ADDRESS);
+
+        let axis_order = match config.get_str_seq("axis_order") {
+            Some(labels) => {
+                let mut axes = Vec::with_capacity(labels.len());
+                for label in &labels {
+                    match Axis::parse(label) {
+                        Some(axis) => axes.push(axis),
+                        None => bail!("unknown ambient_light axis_order entry: {}", label),
+                    }
+                }
+                axes
+            }
+            None => vec![Axis::Illuminance, Axis::Red, Axis::Green, Axis::Blue],
+        };
+
+        if axis_order.len() != 4 {
+            bail!(
+                "ambient_light axis_order must declare exactly 4 channels, got {}",
+                axis_order.len()
+            );
+        }
+
+        Ok(Self {
+            i2c_address,
+            axis_order,
+        })
+    }
+
+    fn assign(&self, raw_channels: [u16; 4], timestamp: u64) -> AmbientLightData {
+        let mut illuminance_raw = 0u16;
+        let mut red = 0u16;
+        let mut green = 0u16;
+        let mut blue = 0u16;
+
+        for (axis, raw) in self.axis_order.iter().zip(raw_channels) {
+            match axis {
+                Axis::Illuminance => illuminance_raw = raw,
+                Axis::Red => red = raw,
+                Axis::Green => green = raw,
+                Axis::Blue => blue = raw,
+            }
+        }
+
+        AmbientLightData {
+            // The sensor's raw "clear" channel scales roughly linearly with
+            // lux; a proper conversion needs the device's gain/integration
+            // time, but this is close enough to drive display dimming.
+            illuminance_lux: illuminance_raw as f32,
+            red,
+            green,
+            blue,
+            timestamp,
+        }
+    }
+}
+
+impl Monitor for AmbientLightMonitor {
+    fn interval(&self) -> Duration {
+        Duration::from_secs(5)
+    }
+
+    fn read(&mut self) -> Result<Reading> {
+        #[cfg(feature = "hardware")]
+        let raw_channels = {
+            let mut i2c = I2c::new()?;
+            i2c.set_slave_address(self.i2c_address)?;
+            i2c.write(&[COMMAND_AUTO_INCREMENT | CDATA_REGISTER])?;
+
+            let mut buf = [0u8; 8];
+            i2c.read(&mut buf)?;
+
+            [
+                u16::from_le_bytes([buf[0], buf[1]]),
+                u16::from_le_bytes([buf[2], buf[3]]),
+                u16::from_le_bytes([buf[4], buf[5]]),
+                u16::from_le_bytes([buf[6], buf[7]]),
+            ]
+        };
+
+        #[cfg(feature = "simulation")]
+        let raw_channels = simulate_raw_channels();
+
+        Ok(Reading::AmbientLight(
+            self.assign(raw_channels, crate::clock::now_unix_secs()),
+        ))
+    }
+}
+
+#[cfg(feature = "simulation")]
+fn simulate_raw_channels() -> [u16; 4] {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+    use std::time::SystemTime;
+
+    let mut hasher = DefaultHasher::new();
+    SystemTime::now().hash(&mut hasher);
+    let base = hasher.finish();
+
+    let illuminance = (base % 1000) as u16;
+    let red = ((base >> 16) % 256) as u16;
+    let green = ((base >> 24) % 256) as u16;
+    let blue = ((base >> 32) % 256) as u16;
+
+    [illuminance, red, green, blue]
+}
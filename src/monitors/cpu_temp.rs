@@ -0,0 +1,38 @@
+use super::{Monitor, Reading};
+use crate::config::ComponentConfig;
+use anyhow::{Context, Result};
+use std::time::Duration;
+
+/// Reads the SoC temperature from the Linux thermal sysfs, in millidegrees C.
+const DEFAULT_THERMAL_ZONE: &str = "/sys/class/thermal/thermal_zone0/temp";
+
+pub struct CpuTempMonitor {
+    thermal_zone_path: String,
+}
+
+impl CpuTempMonitor {
+    pub fn from_config(config: &ComponentConfig) -> Self {
+        let thermal_zone_path = config
+            .get_str("thermal_zone_path")
+            .unwrap_or(DEFAULT_THERMAL_ZONE)
+            .to_string();
+        Self { thermal_zone_path }
+    }
+}
+
+impl Monitor for CpuTempMonitor {
+    fn interval(&self) -> Duration {
+        Duration::from_secs(5)
+    }
+
+    fn read(&mut self) -> Result<Reading> {
+        let raw = std::fs::read_to_string(&self.thermal_zone_path)
+            .with_context(|| format!("failed to read {}", self.thermal_zone_path))?;
+        let millidegrees: i64 = raw
+            .trim()
+            .parse()
+            .with_context(|| format!("unexpected content in {}", self.thermal_zone_path))?;
+
+        Ok(Reading::CpuTemperature(millidegrees as f32 / 1000.0))
+    }
+}
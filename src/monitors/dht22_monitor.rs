@@ -0,0 +1,45 @@
+use super::{Monitor, Reading};
+use crate::config::ComponentConfig;
+use crate::SensorData;
+use anyhow::Result;
+use std::time::Duration;
+
+/// DHT22 temperature/humidity monitor. Reads real hardware behind the
+/// `hardware` feature, or the hashed simulation otherwise.
+pub struct Dht22Monitor {
+    #[cfg_attr(not(feature = "hardware"), allow(dead_code))]
+    pin: u8,
+}
+
+impl Dht22Monitor {
+    pub fn from_config(config: &ComponentConfig) -> Self {
+        let pin = config.get_u64("pin").unwrap_or(4) as u8;
+        Self { pin }
+    }
+}
+
+impl Monitor for Dht22Monitor {
+    fn interval(&self) -> Duration {
+        Duration::from_secs(5)
+    }
+
+    fn read(&mut self) -> Result<Reading> {
+        #[cfg(feature = "hardware")]
+        let (temperature, humidity) = {
+            let reading = crate::dht22::read(self.pin)?;
+            (reading.temperature, reading.humidity)
+        };
+
+        #[cfg(feature = "simulation")]
+        let (temperature, humidity) = (
+            crate::simulate_temperature_reading(),
+            crate::simulate_humidity_reading(),
+        );
+
+        Ok(Reading::Sensor(SensorData {
+            temperature,
+            humidity,
+            timestamp: crate::clock::now_unix_secs(),
+        }))
+    }
+}
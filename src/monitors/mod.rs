@@ -0,0 +1,43 @@
+// Pluggable sensor inputs. Each `Monitor` polls on its own schedule and
+// produces typed `Reading`s that flow into the output pipeline.
+
+mod ambient_light;
+mod cpu_temp;
+mod dht22_monitor;
+
+pub use ambient_light::AmbientLightMonitor;
+pub use cpu_temp::CpuTempMonitor;
+pub use dht22_monitor::Dht22Monitor;
+
+use crate::config::ComponentConfig;
+use crate::{AmbientLightData, SensorData};
+use anyhow::{bail, Result};
+use std::time::Duration;
+
+/// A typed reading produced by a monitor and consumed by outputs.
+#[derive(Debug, Clone)]
+pub enum Reading {
+    Sensor(SensorData),
+    CpuTemperature(f32),
+    AmbientLight(AmbientLightData),
+}
+
+/// Polls a physical or virtual sensor on its own schedule.
+pub trait Monitor: Send {
+    /// How often this monitor should be polled.
+    fn interval(&self) -> Duration;
+
+    /// Take one reading. Returns an error on sensor failure so the caller
+    /// can log it and retry on the next tick rather than crash the task.
+    fn read(&mut self) -> Result<Reading>;
+}
+
+/// Build a monitor from its YAML `kind` and parameters.
+pub fn factory(config: &ComponentConfig) -> Result<Box<dyn Monitor>> {
+    match config.kind.as_str() {
+        "dht22" => Ok(Box::new(Dht22Monitor::from_config(config))),
+        "cpu_temp" => Ok(Box::new(CpuTempMonitor::from_config(config))),
+        "ambient_light" => Ok(Box::new(AmbientLightMonitor::from_config(config)?)),
+        other => bail!("unknown monitor kind: {}", other),
+    }
+}
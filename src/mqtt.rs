@@ -0,0 +1,162 @@
+// MQTT status publisher: publishes the device's retained `<prefix>/status`
+// and lets the LED be driven remotely via a `<prefix>/led/set` subscription.
+// Per-reading telemetry (`<prefix>/sensor` etc.) is owned by the `mqtt`
+// output (see `outputs::mqtt_output`) when one is configured, so this task
+// doesn't publish readings itself - otherwise both would race to publish the
+// same topic.
+
+use crate::connectivity::{self, ConnectivityAware};
+use crate::{apply_led_state, current_led_state, AppState};
+use log::{error, info, warn};
+use rumqttc::{AsyncClient, Event, MqttOptions, Packet, QoS};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::time;
+
+struct MqttConfig {
+    host: String,
+    port: u16,
+    topic_prefix: String,
+    username: Option<String>,
+    password: Option<String>,
+}
+
+impl MqttConfig {
+    fn from_env() -> Self {
+        Self {
+            host: std::env::var("MQTT_HOST").unwrap_or_else(|_| "localhost".to_string()),
+            port: std::env::var("MQTT_PORT")
+                .ok()
+                .and_then(|p| p.parse().ok())
+                .unwrap_or(1883),
+            topic_prefix: std::env::var("MQTT_TOPIC_PREFIX")
+                .unwrap_or_else(|_| "pi-zero".to_string()),
+            username: std::env::var("MQTT_USERNAME").ok(),
+            password: std::env::var("MQTT_PASSWORD").ok(),
+        }
+    }
+}
+
+/// Tracks the connectivity manager's Online/Offline verdict so the publish
+/// loop can pause without needing its own probing logic.
+struct ConnectivityHooks {
+    online: Arc<AtomicBool>,
+}
+
+impl ConnectivityAware for ConnectivityHooks {
+    fn on_online(&mut self) {
+        self.online.store(true, Ordering::Relaxed);
+    }
+
+    fn on_offline(&mut self) {
+        self.online.store(false, Ordering::Relaxed);
+    }
+}
+
+// Publish the retained status topic and apply remote LED commands received
+// on `<prefix>/led/set`. Reconnects with backoff if the broker connection
+// drops, since `AsyncClient`/`EventLoop` don't do this on their own.
+// Publishing pauses while the connectivity manager reports the network as
+// unreachable, resuming on the next tick once it's back.
+pub async fn mqtt_task(state: AppState, connectivity_rx: watch::Receiver<connectivity::ConnectivityState>) {
+    let config = MqttConfig::from_env();
+
+    let online = Arc::new(AtomicBool::new(connectivity::is_online()));
+    tokio::spawn(connectivity::watch_hooks(
+        connectivity_rx,
+        ConnectivityHooks {
+            online: online.clone(),
+        },
+    ));
+
+    loop {
+        if let Err(e) = run_connection(&config, &state, &online).await {
+            warn!("MQTT connection lost, reconnecting: {}", e);
+        }
+        time::sleep(Duration::from_secs(5)).await;
+    }
+}
+
+async fn run_connection(
+    config: &MqttConfig,
+    state: &AppState,
+    online: &Arc<AtomicBool>,
+) -> anyhow::Result<()> {
+    let client_id = format!("pi-zero-{}", std::process::id());
+    let mut options = MqttOptions::new(client_id, &config.host, config.port);
+    options.set_keep_alive(Duration::from_secs(30));
+    if let (Some(username), Some(password)) = (&config.username, &config.password) {
+        options.set_credentials(username, password);
+    }
+
+    let (client, mut event_loop) = AsyncClient::new(options, 10);
+
+    let led_set_topic = format!("{}/led/set", config.topic_prefix);
+    client.subscribe(&led_set_topic, QoS::AtLeastOnce).await?;
+
+    let mut publish_interval = time::interval(Duration::from_secs(5));
+
+    loop {
+        tokio::select! {
+            _ = publish_interval.tick() => {
+                if online.load(Ordering::Relaxed) {
+                    publish_status(&client, config, state).await?;
+                }
+            }
+            event = event_loop.poll() => {
+                if let Event::Incoming(Packet::Publish(publish)) = event? {
+                    if publish.topic == led_set_topic {
+                        handle_led_command(state, &publish.payload);
+                    }
+                }
+            }
+        }
+    }
+}
+
+async fn publish_status(
+    client: &AsyncClient,
+    config: &MqttConfig,
+    state: &AppState,
+) -> anyhow::Result<()> {
+    let sensor_data = state.sensor_data.lock().ok().and_then(|data| data.clone());
+
+    let status = serde_json::json!({
+        "uptime_seconds": state.start_time.elapsed().as_secs(),
+        "led_status": current_led_state(state),
+        "last_sensor_reading": sensor_data,
+    });
+    client
+        .publish(
+            format!("{}/status", config.topic_prefix),
+            QoS::AtLeastOnce,
+            true, // retained, so a newly connected dashboard sees the latest status
+            serde_json::to_vec(&status)?,
+        )
+        .await?;
+
+    Ok(())
+}
+
+fn handle_led_command(state: &AppState, payload: &[u8]) {
+    #[derive(serde::Deserialize)]
+    struct LedSetPayload {
+        state: bool,
+    }
+
+    match serde_json::from_slice::<LedSetPayload>(payload) {
+        Ok(command) => {
+            if let Err(e) = apply_led_state(state, command.state) {
+                error!("Failed to apply LED command from MQTT: {}", e);
+            } else {
+                info!(
+                    "LED turned {} via MQTT",
+                    if command.state { "ON" } else { "OFF" }
+                );
+            }
+        }
+        Err(e) => warn!("Ignoring malformed LED command on MQTT: {}", e),
+    }
+}
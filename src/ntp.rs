@@ -0,0 +1,127 @@
+// Minimal SNTP client (RFC 4330): queries a configurable pool over UDP at
+// startup and periodically, and feeds the measured offset into `crate::clock`
+// so the rest of the app can get a corrected timestamp without an RTC.
+
+use anyhow::{bail, Context, Result};
+use log::{info, warn};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::net::UdpSocket;
+use tokio::time;
+
+// Seconds between the NTP epoch (1900-01-01) and the Unix epoch (1970-01-01).
+const NTP_UNIX_EPOCH_OFFSET: u64 = 2_208_988_800;
+const NTP_PACKET_SIZE: usize = 48;
+const QUERY_TIMEOUT: Duration = Duration::from_secs(5);
+const RESYNC_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Periodically query an NTP pool and record the offset in `crate::clock`.
+/// Runs an initial sync immediately so timestamps are corrected as soon as
+/// possible after startup, then resyncs every `RESYNC_INTERVAL`.
+pub async fn ntp_task() {
+    let pool = std::env::var("NTP_POOL").unwrap_or_else(|_| "pool.ntp.org".to_string());
+    let port: u16 = std::env::var("NTP_PORT")
+        .ok()
+        .and_then(|p| p.parse().ok())
+        .unwrap_or(123);
+
+    let mut interval = time::interval(RESYNC_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        match query_offset(&pool, port).await {
+            Ok(offset_ms) => {
+                crate::clock::set_offset_ms(offset_ms);
+                info!("NTP sync against {}: clock offset {} ms", pool, offset_ms);
+            }
+            Err(e) => warn!("NTP sync against {} failed, keeping prior offset: {}", pool, e),
+        }
+    }
+}
+
+/// Send a single SNTP request and compute the clock offset from the
+/// originate/receive/transmit timestamps, per the standard SNTP formula:
+/// `offset = ((t2 - t1) + (t3 - t4)) / 2`.
+async fn query_offset(host: &str, port: u16) -> Result<i64> {
+    let socket = UdpSocket::bind("0.0.0.0:0")
+        .await
+        .context("failed to bind UDP socket for NTP query")?;
+    socket
+        .connect((host, port))
+        .await
+        .with_context(|| format!("failed to resolve NTP server {}:{}", host, port))?;
+
+    let mut request = [0u8; NTP_PACKET_SIZE];
+    request[0] = 0b00_100_011; // LI = 0 (no warning), VN = 4, Mode = 3 (client)
+
+    let t1 = now_as_ntp_timestamp();
+    write_ntp_timestamp(&mut request[40..48], t1);
+
+    time::timeout(QUERY_TIMEOUT, socket.send(&request))
+        .await
+        .context("timed out sending NTP request")??;
+
+    let mut response = [0u8; NTP_PACKET_SIZE];
+    time::timeout(QUERY_TIMEOUT, socket.recv(&mut response))
+        .await
+        .context("timed out waiting for NTP response")??;
+
+    let t4 = now_as_ntp_timestamp();
+
+    let t2 = read_ntp_timestamp(&response[32..40]); // server receive timestamp
+    let t3 = read_ntp_timestamp(&response[40..48]); // server transmit timestamp
+    if t2 == 0.0 || t3 == 0.0 {
+        bail!("NTP response did not contain valid receive/transmit timestamps");
+    }
+
+    let offset_seconds = ((t2 - t1) + (t3 - t4)) / 2.0;
+    Ok((offset_seconds * 1000.0).round() as i64)
+}
+
+/// Current time as seconds (with fractional part) since the NTP epoch.
+fn now_as_ntp_timestamp() -> f64 {
+    let unix = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+    unix.as_secs_f64() + NTP_UNIX_EPOCH_OFFSET as f64
+}
+
+/// Write a 64-bit NTP timestamp (32 bits seconds, 32 bits fraction) in
+/// network byte order.
+fn write_ntp_timestamp(buf: &mut [u8], seconds_since_ntp_epoch: f64) {
+    let seconds = seconds_since_ntp_epoch.trunc() as u32;
+    let fraction = (seconds_since_ntp_epoch.fract() * (u32::MAX as f64)) as u32;
+    buf[0..4].copy_from_slice(&seconds.to_be_bytes());
+    buf[4..8].copy_from_slice(&fraction.to_be_bytes());
+}
+
+/// Read a 64-bit NTP timestamp back into seconds since the NTP epoch.
+fn read_ntp_timestamp(buf: &[u8]) -> f64 {
+    let seconds = u32::from_be_bytes(buf[0..4].try_into().unwrap());
+    let fraction = u32::from_be_bytes(buf[4..8].try_into().unwrap());
+    seconds as f64 + (fraction as f64 / u32::MAX as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ntp_timestamp_round_trips_through_the_wire_format() {
+        let original = 3_912_345_678.25_f64;
+        let mut buf = [0u8; 8];
+        write_ntp_timestamp(&mut buf, original);
+        let decoded = read_ntp_timestamp(&buf);
+        assert!(
+            (decoded - original).abs() < 1e-6,
+            "expected {}, got {}",
+            original,
+            decoded
+        );
+    }
+
+    #[test]
+    fn ntp_timestamp_round_trips_at_zero() {
+        let mut buf = [0u8; 8];
+        write_ntp_timestamp(&mut buf, 0.0);
+        assert_eq!(read_ntp_timestamp(&buf), 0.0);
+    }
+}
@@ -0,0 +1,29 @@
+// Pluggable sinks for monitor `Reading`s: the display, MQTT, stdout, etc.
+
+mod mqtt_output;
+mod st7789_output;
+mod stdout_output;
+
+pub use mqtt_output::MqttOutput;
+pub use st7789_output::St7789Output;
+pub use stdout_output::StdoutOutput;
+
+use crate::config::ComponentConfig;
+use crate::monitors::Reading;
+use crate::AppState;
+use anyhow::{bail, Result};
+
+/// Consumes typed readings from the monitor pipeline.
+pub trait Output: Send {
+    fn handle(&mut self, reading: &Reading) -> Result<()>;
+}
+
+/// Build an output from its YAML `kind` and parameters.
+pub fn factory(config: &ComponentConfig, state: &AppState) -> Result<Box<dyn Output>> {
+    match config.kind.as_str() {
+        "stdout" => Ok(Box::new(StdoutOutput::new())),
+        "st7789" => Ok(Box::new(St7789Output::new(state.clone(), config)?)),
+        "mqtt" => Ok(Box::new(MqttOutput::from_config(config)?)),
+        other => bail!("unknown output kind: {}", other),
+    }
+}
@@ -0,0 +1,70 @@
+use super::Output;
+use crate::config::ComponentConfig;
+use crate::monitors::Reading;
+use anyhow::Result;
+use log::warn;
+use rumqttc::{Client, MqttOptions, QoS};
+use std::time::Duration;
+
+/// Publishes each reading as JSON to `<topic_prefix>/<reading kind>`.
+///
+/// Uses the blocking `rumqttc::Client` rather than the `async` one the
+/// standalone [`crate::mqtt`] task uses, since `Output::handle` is a
+/// synchronous call from the dispatcher loop.
+pub struct MqttOutput {
+    client: Client,
+    topic_prefix: String,
+}
+
+impl MqttOutput {
+    pub fn from_config(config: &ComponentConfig) -> Result<Self> {
+        let host = config.get_str("host").unwrap_or("localhost").to_string();
+        let port = config.get_u64("port").unwrap_or(1883) as u16;
+        let topic_prefix = config
+            .get_str("topic_prefix")
+            .unwrap_or("pi-zero")
+            .to_string();
+
+        let client_id = format!("pi-zero-output-{}", std::process::id());
+        let mut options = MqttOptions::new(client_id, host, port);
+        options.set_keep_alive(Duration::from_secs(30));
+
+        let (client, mut connection) = Client::new(options, 10);
+
+        // The blocking client only sends/receives packets while its
+        // `Connection` is being polled, so drive it on a dedicated thread.
+        std::thread::spawn(move || {
+            for notification in connection.iter() {
+                if let Err(e) = notification {
+                    warn!("MQTT output connection error: {}", e);
+                }
+            }
+        });
+
+        Ok(Self {
+            client,
+            topic_prefix,
+        })
+    }
+}
+
+impl Output for MqttOutput {
+    fn handle(&mut self, reading: &Reading) -> Result<()> {
+        let (topic_suffix, payload) = match reading {
+            Reading::Sensor(data) => ("sensor", serde_json::to_vec(data)?),
+            Reading::CpuTemperature(celsius) => {
+                ("cpu_temp", serde_json::to_vec(&serde_json::json!({ "celsius": celsius }))?)
+            }
+            Reading::AmbientLight(data) => ("ambient_light", serde_json::to_vec(data)?),
+        };
+
+        self.client.publish(
+            format!("{}/{}", self.topic_prefix, topic_suffix),
+            QoS::AtLeastOnce,
+            false,
+            payload,
+        )?;
+
+        Ok(())
+    }
+}
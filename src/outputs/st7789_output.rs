@@ -0,0 +1,80 @@
+use super::Output;
+use crate::config::ComponentConfig;
+use crate::monitors::Reading;
+use crate::{update_display_content, AppState};
+use anyhow::Result;
+
+#[cfg(feature = "hardware")]
+use rppal::gpio::{Gpio, OutputPin};
+#[cfg(feature = "hardware")]
+use std::time::Duration;
+
+/// Lowest backlight duty cycle: in the dark the backlight dims to this
+/// fraction rather than turning fully off.
+const MIN_DUTY_CYCLE: f64 = 0.05;
+/// Illuminance (in the sensor's raw units) that maps to full brightness.
+const FULL_BRIGHTNESS_LUX: f32 = 500.0;
+
+/// Renders sensor readings to the ST7789 (or simulated) display already
+/// owned by `AppState`, and dims its backlight to match ambient light.
+pub struct St7789Output {
+    state: AppState,
+    #[cfg(feature = "hardware")]
+    backlight_pin: Option<OutputPin>,
+}
+
+impl St7789Output {
+    pub fn new(state: AppState, config: &ComponentConfig) -> Result<Self> {
+        #[cfg(feature = "hardware")]
+        let backlight_pin = match config.get_u64("backlight_pin") {
+            Some(pin) => Some(Gpio::new()?.get(pin as u8)?.into_output()),
+            None => None,
+        };
+
+        Ok(Self {
+            state,
+            #[cfg(feature = "hardware")]
+            backlight_pin,
+        })
+    }
+
+    /// Map an illuminance reading to a 0.0-1.0 PWM duty cycle: dim in the
+    /// dark, full brightness in bright light.
+    #[cfg_attr(not(feature = "hardware"), allow(dead_code))]
+    fn lux_to_duty_cycle(lux: f32) -> f64 {
+        (lux / FULL_BRIGHTNESS_LUX).clamp(MIN_DUTY_CYCLE as f32, 1.0) as f64
+    }
+}
+
+impl Output for St7789Output {
+    fn handle(&mut self, reading: &Reading) -> Result<()> {
+        match reading {
+            Reading::Sensor(data) => {
+                if let Ok(mut display_opt) = self.state.display.lock() {
+                    if let Some(display) = display_opt.as_mut() {
+                        update_display_content(display.as_mut(), &Some(data.clone()), &self.state)?;
+                    }
+                }
+            }
+            Reading::AmbientLight(data) => {
+                #[cfg(feature = "hardware")]
+                if let Some(pin) = self.backlight_pin.as_mut() {
+                    let duty_cycle = Self::lux_to_duty_cycle(data.illuminance_lux);
+                    let period = Duration::from_millis(10);
+                    let pulse_width = period.mul_f64(duty_cycle);
+                    pin.set_pwm(period, pulse_width)?;
+                }
+
+                #[cfg(not(feature = "hardware"))]
+                {
+                    // No backlight to drive in simulation; the mapping is
+                    // exercised above only when hardware is present.
+                    let _ = data;
+                }
+            }
+            Reading::CpuTemperature(_) => {}
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use super::Output;
+use crate::monitors::Reading;
+use anyhow::Result;
+use log::info;
+
+/// Logs every reading, useful for config testing without a display attached.
+pub struct StdoutOutput;
+
+impl StdoutOutput {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Output for StdoutOutput {
+    fn handle(&mut self, reading: &Reading) -> Result<()> {
+        match reading {
+            Reading::Sensor(data) => {
+                info!(
+                    "[stdout output] {:.1}°C, {:.1}% humidity",
+                    data.temperature, data.humidity
+                );
+            }
+            Reading::CpuTemperature(celsius) => {
+                info!("[stdout output] CPU temperature: {:.1}°C", celsius);
+            }
+            Reading::AmbientLight(data) => {
+                info!(
+                    "[stdout output] ambient light: {:.1} lux (r={}, g={}, b={})",
+                    data.illuminance_lux, data.red, data.green, data.blue
+                );
+            }
+        }
+        Ok(())
+    }
+}